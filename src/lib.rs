@@ -25,7 +25,12 @@
 //! )
 //! ```
 
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    ffi::OsStr,
+    fmt,
+};
 
 /// Parser struct containing the config, a map of arguments to functions, and a function that is
 /// called when an argument is not in the map.
@@ -59,7 +64,15 @@ use std::{borrow::Cow, collections::HashMap};
 pub struct Parser<'a, Config: 'a> {
     config: Config,
     arguments: HashMap<Cow<'a, str>, Box<dyn Fn(&mut Config) + 'a>>,
+    value_arguments: HashMap<Cow<'a, str>, Box<dyn Fn(&mut Config, &'a str) + 'a>>,
+    subcommands:
+        HashMap<Cow<'a, str>, Box<dyn Fn(&mut Config, &mut dyn Iterator<Item = &'a str>) + 'a>>,
+    descriptions: HashMap<Cow<'a, str>, Cow<'a, str>>,
+    requirements: HashMap<Cow<'a, str>, Vec<Cow<'a, str>>>,
+    conflicts: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    required: Vec<Cow<'a, str>>,
     unknown: Box<dyn Fn(&mut Config, &'a str) + 'a>,
+    unknown_os: Box<dyn Fn(&mut Config, &'a OsStr) + 'a>,
 }
 
 impl<'a, Config: 'a> Parser<'a, Config> {
@@ -67,7 +80,14 @@ impl<'a, Config: 'a> Parser<'a, Config> {
         Self {
             config,
             arguments: HashMap::new(),
+            value_arguments: HashMap::new(),
+            subcommands: HashMap::new(),
+            descriptions: HashMap::new(),
+            requirements: HashMap::new(),
+            conflicts: Vec::new(),
+            required: Vec::new(),
             unknown: Box::new(unknown),
+            unknown_os: Box::new(|_, _| {}),
         }
     }
 
@@ -80,7 +100,14 @@ impl<'a, Config: 'a> Parser<'a, Config> {
         Self {
             config,
             arguments,
+            value_arguments: HashMap::new(),
+            subcommands: HashMap::new(),
+            descriptions: HashMap::new(),
+            requirements: HashMap::new(),
+            conflicts: Vec::new(),
+            required: Vec::new(),
             unknown: Box::new(unknown),
+            unknown_os: Box::new(|_, _| {}),
         }
     }
 
@@ -93,16 +120,542 @@ impl<'a, Config: 'a> Parser<'a, Config> {
         self
     }
 
+    /// Registers an argument that consumes the token following it, e.g. `--output file.txt`.
+    ///
+    /// When [`Parser::parse`] encounters this argument, it pulls the next token from the input
+    /// and passes it to `callback`. If there is no next token, the argument itself is routed to
+    /// the `unknown` handler instead, since a dangling value-consuming flag has no value to act
+    /// on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(PartialEq, Debug, Default)]
+    /// struct Config {
+    ///     output: String,
+    /// }
+    ///
+    /// let cfg = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg_value("--output", |cfg, value| cfg.output = value.to_string())
+    ///     .parse(["--output", "file.txt"]);
+    ///
+    /// assert_eq!(
+    ///     cfg,
+    ///     Config {
+    ///         output: "file.txt".to_string(),
+    ///     }
+    /// )
+    /// ```
+    pub fn arg_value(
+        mut self,
+        argument: impl Into<Cow<'a, str>>,
+        callback: impl Fn(&mut Config, &'a str) + 'a,
+    ) -> Self {
+        self.value_arguments
+            .insert(argument.into(), Box::new(callback));
+        self
+    }
+
+    /// Registers a subcommand that hands the remaining tokens to a nested handler, e.g. `git
+    /// add ...` / `git commit ...`.
+    ///
+    /// When [`Parser::parse`] encounters this token, it passes `&mut Config` together with the
+    /// rest of the token iterator to `handler`, which is free to drive its own [`Parser`] (or any
+    /// other logic) over what remains. Tokens the handler consumes are not matched at the top
+    /// level; any it leaves unconsumed are matched at the top level again once it returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(PartialEq, Debug, Default)]
+    /// struct Config {
+    ///     added: Vec<String>,
+    /// }
+    ///
+    /// let cfg = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .subcommand("add", |cfg, rest| {
+    ///         for path in rest {
+    ///             cfg.added.push(path.to_string());
+    ///         }
+    ///     })
+    ///     .parse(["add", "a.txt", "b.txt"]);
+    ///
+    /// assert_eq!(
+    ///     cfg,
+    ///     Config {
+    ///         added: vec!["a.txt".to_string(), "b.txt".to_string()],
+    ///     }
+    /// )
+    /// ```
+    pub fn subcommand(
+        mut self,
+        name: impl Into<Cow<'a, str>>,
+        handler: impl Fn(&mut Config, &mut dyn Iterator<Item = &'a str>) + 'a,
+    ) -> Self {
+        self.subcommands.insert(name.into(), Box::new(handler));
+        self
+    }
+
+    /// Attaches help text to a previously (or subsequently) registered argument, to be shown by
+    /// [`Parser::help`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(Default)]
+    /// struct Config {
+    ///     foo: bool,
+    /// }
+    ///
+    /// let parser = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg("-foo", |cfg| cfg.foo = true)
+    ///     .describe("-foo", "Enable foo");
+    ///
+    /// assert!(parser.help("mytool").contains("Enable foo"));
+    /// ```
+    pub fn describe(
+        mut self,
+        argument: impl Into<Cow<'a, str>>,
+        help: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.descriptions.insert(argument.into(), help.into());
+        self
+    }
+
+    /// Renders a minimal `--help`-style listing of every registered argument and its
+    /// [`Parser::describe`]d help text, in two aligned columns.
+    ///
+    /// Value-consuming arguments ([`Parser::arg_value`]) are shown with a trailing `<value>`
+    /// placeholder to indicate they expect a following argument.
+    ///
+    /// This only renders the listing; it does not register a `--help` argument or exit the
+    /// process. To offer `--help` as a flag, register it like any other argument and have its
+    /// callback record that help was requested (e.g. a `bool` field on `Config`), call `help`
+    /// before parsing (which consumes the parser), and print it once parsing finishes.
+    pub fn help(&self, bin_name: &str) -> String {
+        let mut entries: Vec<(String, &str)> = self
+            .arguments
+            .keys()
+            .map(|key| (key.to_string(), self.describe_of(key)))
+            .chain(
+                self.value_arguments
+                    .keys()
+                    .map(|key| (format!("{key} <value>"), self.describe_of(key))),
+            )
+            .collect();
+        entries.sort_unstable();
+
+        let width = entries
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut output = format!("Usage: {bin_name} [OPTIONS]\n\n");
+        for (name, description) in entries {
+            output.push_str(&format!("    {name:width$}    {description}\n"));
+        }
+
+        output
+    }
+
+    fn describe_of(&self, argument: &str) -> &str {
+        self.descriptions
+            .get(argument)
+            .map(Cow::as_ref)
+            .unwrap_or("")
+    }
+
+    /// Declares that, if `argument` is seen by [`Parser::try_parse`], `requires` must be seen
+    /// too, otherwise parsing fails with [`ParseError::UnsatisfiedRequirement`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(Debug, Default)]
+    /// struct Config {
+    ///     verbose: bool,
+    ///     format: String,
+    /// }
+    ///
+    /// let result = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg("-v", |cfg| cfg.verbose = true)
+    ///     .arg_value("--format", |cfg, value| cfg.format = value.to_string())
+    ///     .requires("-v", "--format")
+    ///     .try_parse(["-v"]);
+    ///
+    /// assert_eq!(
+    ///     result.unwrap_err(),
+    ///     arg_fn::ParseError::UnsatisfiedRequirement("-v".to_string(), "--format".to_string())
+    /// );
+    /// ```
+    pub fn requires(
+        mut self,
+        argument: impl Into<Cow<'a, str>>,
+        requires: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.requirements
+            .entry(argument.into())
+            .or_default()
+            .push(requires.into());
+        self
+    }
+
+    /// Declares that `a` and `b` cannot both be seen by [`Parser::try_parse`], otherwise parsing
+    /// fails with [`ParseError::Conflict`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(Debug, Default)]
+    /// struct Config {
+    ///     verbose: bool,
+    ///     quiet: bool,
+    /// }
+    ///
+    /// let result = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg("-v", |cfg| cfg.verbose = true)
+    ///     .arg("-q", |cfg| cfg.quiet = true)
+    ///     .conflicts("-v", "-q")
+    ///     .try_parse(["-v", "-q"]);
+    ///
+    /// assert_eq!(
+    ///     result.unwrap_err(),
+    ///     arg_fn::ParseError::Conflict("-v".to_string(), "-q".to_string())
+    /// );
+    /// ```
+    pub fn conflicts(mut self, a: impl Into<Cow<'a, str>>, b: impl Into<Cow<'a, str>>) -> Self {
+        self.conflicts.push((a.into(), b.into()));
+        self
+    }
+
+    /// Declares that `argument` must be seen by [`Parser::try_parse`], otherwise parsing fails
+    /// with [`ParseError::MissingRequired`].
+    pub fn required(mut self, argument: impl Into<Cow<'a, str>>) -> Self {
+        self.required.push(argument.into());
+        self
+    }
+
+    /// Sets the handler [`Parser::parse_os`] calls for a token that is either unregistered or
+    /// not valid UTF-8. Defaults to a no-op.
+    pub fn unknown_os(mut self, unknown_os: impl Fn(&mut Config, &'a OsStr) + 'a) -> Self {
+        self.unknown_os = Box::new(unknown_os);
+        self
+    }
+
+    /// Parses `input`, dispatching each token to its registered callback.
+    ///
+    /// A token is resolved in the following order of precedence:
+    ///
+    /// 1. An exact match in the flag ([`Parser::arg`]) or value ([`Parser::arg_value`]) registry.
+    /// 2. An exact match in the [`Parser::subcommand`] registry, in which case the rest of the
+    ///    input is handed to the subcommand's handler; tokens it leaves unconsumed are matched
+    ///    at the top level again.
+    /// 3. If the token contains a `=`, the left side split at the first `=` is looked up in the
+    ///    value registry and the right side is passed as its value.
+    /// 4. If the token is a single-dash cluster of short flags (e.g. `-abc`) and *every* expanded
+    ///    short (`-a`, `-b`, `-c`) exists in the flag registry, each is dispatched in order.
+    /// 5. Otherwise, the token is routed to the `unknown` handler. A value-consuming argument
+    ///    with no following token also falls through to `unknown`, since it has no value to act
+    ///    on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(PartialEq, Debug, Default)]
+    /// struct Config {
+    ///     output: String,
+    ///     foo: bool,
+    ///     bar: bool,
+    /// }
+    ///
+    /// let cfg = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg_value("--output", |cfg, value| cfg.output = value.to_string())
+    ///     .arg("-f", |cfg| cfg.foo = true)
+    ///     .arg("-b", |cfg| cfg.bar = true)
+    ///     .parse(["--output=file.txt", "-fb"]);
+    ///
+    /// assert_eq!(
+    ///     cfg,
+    ///     Config {
+    ///         output: "file.txt".to_string(),
+    ///         foo: true,
+    ///         bar: true,
+    ///     }
+    /// )
+    /// ```
     pub fn parse(mut self, input: impl IntoIterator<Item = &'a str>) -> Config {
-        for arg in input {
-            if let Some(callback) = self.arguments.get(arg) {
-                callback(&mut self.config);
+        let mut input = input.into_iter();
+
+        while let Some(arg) = input.next() {
+            self.dispatch(arg, &mut input);
+        }
+
+        self.config
+    }
+
+    /// Like [`Parser::parse`], but validates the relationships declared with
+    /// [`Parser::requires`], [`Parser::conflicts`], and [`Parser::required`] once parsing
+    /// finishes, returning a [`ParseError`] if any relationship is violated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(Debug, Default)]
+    /// struct Config {
+    ///     verbose: bool,
+    /// }
+    ///
+    /// let result = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg("-v", |cfg| cfg.verbose = true)
+    ///     .required("-v")
+    ///     .try_parse([]);
+    ///
+    /// assert_eq!(result.unwrap_err(), arg_fn::ParseError::MissingRequired("-v".to_string()));
+    /// ```
+    pub fn try_parse(
+        mut self,
+        input: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Config, ParseError> {
+        let mut seen = HashSet::new();
+        let mut input = input.into_iter();
+
+        while let Some(arg) = input.next() {
+            if let Some(matched) = self.dispatch(arg, &mut input) {
+                seen.extend(matched);
+            }
+        }
+
+        for argument in &self.required {
+            if !seen.contains(argument.as_ref()) {
+                return Err(ParseError::MissingRequired(argument.to_string()));
+            }
+        }
+
+        for (a, b) in &self.conflicts {
+            if seen.contains(a.as_ref()) && seen.contains(b.as_ref()) {
+                return Err(ParseError::Conflict(a.to_string(), b.to_string()));
+            }
+        }
+
+        for (argument, requires) in &self.requirements {
+            if !seen.contains(argument.as_ref()) {
+                continue;
+            }
+            for required in requires {
+                if !seen.contains(required.as_ref()) {
+                    return Err(ParseError::UnsatisfiedRequirement(
+                        argument.to_string(),
+                        required.to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(self.config)
+    }
+
+    /// Parses `input` given as `OsStr`, for callers driving real `std::env::args_os()` where
+    /// tokens (paths, locale-dependent bytes) may not be valid UTF-8.
+    ///
+    /// Each token is matched against the flag ([`Parser::arg`]) and value ([`Parser::arg_value`])
+    /// registries by attempting UTF-8 conversion; a token that isn't valid UTF-8 or doesn't match
+    /// a registered key is routed to the [`Parser::unknown_os`] handler with the original
+    /// `OsStr` intact. For a value-consuming argument, if the *following* token is missing, the
+    /// flag itself is routed to `unknown_os`; if it is present but not valid UTF-8, that value
+    /// token (not the flag) is routed to `unknown_os`, so the raw bytes are never dropped. Unlike
+    /// [`Parser::parse`], this path does not support `=`-splitting, short clustering, or
+    /// subcommands, since those features key off of UTF-8 string matching that non-UTF-8 tokens
+    /// cannot satisfy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::OsStr;
+    ///
+    /// #[derive(PartialEq, Debug, Default)]
+    /// struct Config {
+    ///     output: String,
+    /// }
+    ///
+    /// let cfg = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg_value("--output", |cfg, value| cfg.output = value.to_string())
+    ///     .parse_os([OsStr::new("--output"), OsStr::new("file.txt")]);
+    ///
+    /// assert_eq!(
+    ///     cfg,
+    ///     Config {
+    ///         output: "file.txt".to_string(),
+    ///     }
+    /// )
+    /// ```
+    ///
+    /// A non-UTF-8 value is forwarded to `unknown_os` intact instead of being dropped:
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # fn main() {
+    /// use std::ffi::OsStr;
+    /// use std::os::unix::ffi::OsStrExt;
+    ///
+    /// #[derive(Debug, Default)]
+    /// struct Config {
+    ///     output: String,
+    ///     unknowns: Vec<String>,
+    /// }
+    ///
+    /// let invalid = OsStr::from_bytes(&[b'f', b'i', b'l', b'e', 0xFF, b't', b'x', b't']);
+    ///
+    /// let cfg = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg_value("--output", |cfg, value| cfg.output = value.to_string())
+    ///     .unknown_os(|cfg, arg| cfg.unknowns.push(arg.to_string_lossy().into_owned()))
+    ///     .parse_os([OsStr::new("--output"), invalid]);
+    ///
+    /// assert_eq!(cfg.output, "");
+    /// assert_eq!(cfg.unknowns, vec!["file\u{FFFD}txt".to_string()]);
+    /// # }
+    /// # #[cfg(not(unix))]
+    /// # fn main() {}
+    /// ```
+    pub fn parse_os(mut self, input: impl IntoIterator<Item = &'a OsStr>) -> Config {
+        let mut input = input.into_iter();
+
+        while let Some(arg) = input.next() {
+            match arg.to_str() {
+                Some(key) if self.arguments.contains_key(key) => {
+                    (self.arguments[key])(&mut self.config);
+                }
+                Some(key) if self.value_arguments.contains_key(key) => match input.next() {
+                    Some(value) => match value.to_str() {
+                        Some(value) => (self.value_arguments[key])(&mut self.config, value),
+                        None => (self.unknown_os)(&mut self.config, value),
+                    },
+                    None => (self.unknown_os)(&mut self.config, arg),
+                },
+                _ => (self.unknown_os)(&mut self.config, arg),
+            }
+        }
+
+        self.config
+    }
+
+    /// Resolves and dispatches a single token per the precedence documented on
+    /// [`Parser::parse`], pulling a following token from `input` for value-consuming arguments
+    /// and subcommands as needed.
+    ///
+    /// Returns the argument keys that were matched (a single key for a flag, value-arg, `=`-split
+    /// value-arg, or subcommand; possibly several for a short cluster), or `None` if the token
+    /// fell through to `unknown`. [`Parser::try_parse`] uses this to build its seen-set without
+    /// duplicating the precedence chain.
+    fn dispatch(
+        &mut self,
+        arg: &'a str,
+        input: &mut dyn Iterator<Item = &'a str>,
+    ) -> Option<Vec<String>> {
+        if self.arguments.contains_key(arg) {
+            (self.arguments[arg])(&mut self.config);
+            Some(vec![arg.to_string()])
+        } else if self.value_arguments.contains_key(arg) {
+            if let Some(value) = input.next() {
+                (self.value_arguments[arg])(&mut self.config, value);
+                Some(vec![arg.to_string()])
             } else {
                 (self.unknown)(&mut self.config, arg);
-            };
+                None
+            }
+        } else if self.subcommands.contains_key(arg) {
+            (self.subcommands[arg])(&mut self.config, input);
+            Some(vec![arg.to_string()])
+        } else if let Some((key, value)) = arg.split_once('=') {
+            if self.value_arguments.contains_key(key) {
+                (self.value_arguments[key])(&mut self.config, value);
+                Some(vec![key.to_string()])
+            } else {
+                self.dispatch_unknown_or_cluster(arg)
+            }
+        } else {
+            self.dispatch_unknown_or_cluster(arg)
+        }
+    }
+
+    /// Attempts to expand a single-dash token (e.g. `-abc`) into clustered short flags (`-a`,
+    /// `-b`, `-c`), dispatching each if every expanded short is registered. Falls back to the
+    /// `unknown` handler for the whole token otherwise.
+    ///
+    /// Returns the expanded shorts that were dispatched, or `None` if the token fell through to
+    /// `unknown`, so callers that track which arguments were seen (e.g. [`Parser::try_parse`])
+    /// know what to record.
+    fn dispatch_unknown_or_cluster(&mut self, arg: &'a str) -> Option<Vec<String>> {
+        let is_cluster_candidate = arg.len() > 2 && arg.starts_with('-') && !arg.starts_with("--");
+
+        if is_cluster_candidate {
+            let shorts: Vec<String> = arg[1..].chars().map(|c| format!("-{c}")).collect();
+            if shorts
+                .iter()
+                .all(|s| self.arguments.contains_key(s.as_str()))
+            {
+                for s in &shorts {
+                    (self.arguments[s.as_str()])(&mut self.config);
+                }
+                return Some(shorts);
+            }
         }
 
-        self.config
+        (self.unknown)(&mut self.config, arg);
+        None
+    }
+
+    /// Generates a shell completion script offering every registered argument as a candidate,
+    /// mirroring clap's `completions` module.
+    ///
+    /// Value-consuming arguments ([`Parser::arg_value`]) are marked so the shell expects a
+    /// following argument (a file, in the absence of more specific knowledge) rather than
+    /// another flag. [`Parser::subcommand`] names are offered as plain candidates alongside
+    /// flags.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(Default)]
+    /// struct Config {
+    ///     foo: bool,
+    /// }
+    ///
+    /// let parser = arg_fn::Parser::new(Config::default(), |_cfg, _arg| {})
+    ///     .arg("-foo", |cfg| cfg.foo = true)
+    ///     .arg_value("--output", |_cfg, _value| {})
+    ///     .subcommand("add", |_cfg, _rest| {});
+    ///
+    /// let bash = parser.completions(arg_fn::Shell::Bash, "mytool");
+    /// assert!(bash.contains("-foo"));
+    /// assert!(bash.contains("--output"));
+    /// assert!(bash.contains("add"));
+    ///
+    /// let zsh = parser.completions(arg_fn::Shell::Zsh, "mytool");
+    /// assert!(zsh.contains("'-foo[-foo]'"));
+    /// assert!(zsh.contains("'--output[--output]:value:_files'"));
+    ///
+    /// let fish = parser.completions(arg_fn::Shell::Fish, "mytool");
+    /// assert!(fish.contains("complete -c mytool -a '-foo'"));
+    /// assert!(fish.contains("complete -c mytool -a '--output' -r"));
+    /// ```
+    pub fn completions(&self, shell: Shell, bin_name: &str) -> String {
+        let mut flags: Vec<&str> = self
+            .arguments
+            .keys()
+            .chain(self.subcommands.keys())
+            .map(Cow::as_ref)
+            .collect();
+        flags.sort_unstable();
+
+        let mut values: Vec<&str> = self.value_arguments.keys().map(Cow::as_ref).collect();
+        values.sort_unstable();
+
+        match shell {
+            Shell::Bash => completions_bash(bin_name, &flags, &values),
+            Shell::Zsh => completions_zsh(bin_name, &flags, &values),
+            Shell::Fish => completions_fish(bin_name, &flags, &values),
+        }
     }
 }
 
@@ -111,3 +664,104 @@ impl<'a, Config: Default> Default for Parser<'a, Config> {
         Self::new(Config::default(), |_, _| {})
     }
 }
+
+/// Shell flavour to target when generating completion scripts with [`Parser::completions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+fn completions_bash(bin_name: &str, flags: &[&str], values: &[&str]) -> String {
+    let opts = flags
+        .iter()
+        .chain(values)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ");
+    // An empty `case` pattern is a bash syntax error, so the block is left out when no
+    // value-consuming arguments exist.
+    let value_case = if values.is_empty() {
+        String::new()
+    } else {
+        let value_pattern = values.join("|");
+        format!(
+            "\x20\x20\x20\x20case \"${{prev}}\" in\n\
+            \x20\x20\x20\x20\x20\x20\x20\x20{value_pattern})\n\
+            \x20\x20\x20\x20\x20\x20\x20\x20\x20\x20\x20\x20COMPREPLY=( $(compgen -f -- \"${{cur}}\") )\n\
+            \x20\x20\x20\x20\x20\x20\x20\x20\x20\x20\x20\x20return 0\n\
+            \x20\x20\x20\x20\x20\x20\x20\x20\x20\x20;;\n\
+            \x20\x20\x20\x20esac\n\
+            \n"
+        )
+    };
+
+    format!(
+        "_{bin_name}_completions() {{\n\
+        \x20\x20\x20\x20local cur prev opts\n\
+        \x20\x20\x20\x20COMPREPLY=()\n\
+        \x20\x20\x20\x20cur=\"${{COMP_WORDS[COMP_CWORD]}}\"\n\
+        \x20\x20\x20\x20prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"\n\
+        \x20\x20\x20\x20opts=\"{opts}\"\n\
+        \n\
+        {value_case}\
+        \x20\x20\x20\x20COMPREPLY=( $(compgen -W \"${{opts}}\" -- \"${{cur}}\") )\n\
+        \x20\x20\x20\x20return 0\n\
+        }}\n\
+        complete -F _{bin_name}_completions {bin_name}\n"
+    )
+}
+
+fn completions_zsh(bin_name: &str, flags: &[&str], values: &[&str]) -> String {
+    let mut lines = String::new();
+    for flag in flags {
+        lines.push_str(&format!("    '{flag}[{flag}]' \\\n"));
+    }
+    for value in values {
+        lines.push_str(&format!("    '{value}[{value}]:value:_files' \\\n"));
+    }
+
+    format!("#compdef {bin_name}\n\n_arguments -s \\\n{lines}\n")
+}
+
+fn completions_fish(bin_name: &str, flags: &[&str], values: &[&str]) -> String {
+    let mut lines = String::new();
+    for flag in flags {
+        lines.push_str(&format!("complete -c {bin_name} -a '{flag}'\n"));
+    }
+    for value in values {
+        lines.push_str(&format!("complete -c {bin_name} -a '{value}' -r\n"));
+    }
+    lines
+}
+
+/// Error returned by [`Parser::try_parse`] when a declared [`Parser::requires`],
+/// [`Parser::conflicts`], or [`Parser::required`] relationship is violated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// An argument declared with [`Parser::required`] was not seen.
+    MissingRequired(String),
+    /// Two arguments declared with [`Parser::conflicts`] were both seen.
+    Conflict(String, String),
+    /// An argument was seen, but one it [`Parser::requires`] was not.
+    UnsatisfiedRequirement(String, String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingRequired(argument) => {
+                write!(f, "missing required argument `{argument}`")
+            }
+            ParseError::Conflict(a, b) => {
+                write!(f, "argument `{a}` conflicts with `{b}`")
+            }
+            ParseError::UnsatisfiedRequirement(argument, requires) => {
+                write!(f, "argument `{argument}` requires `{requires}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}